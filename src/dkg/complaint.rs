@@ -0,0 +1,250 @@
+//! GJKR-style complaint resolution: a participant that receives an
+//! inconsistent share broadcasts a complaint naming the dealer, the dealer
+//! gets a chance to reveal the disputed share in the clear, and dealers that
+//! fail to clear every complaint against them are dropped from `QUAL`.
+
+use curve25519_dalek::scalar::Scalar;
+use rand::{CryptoRng, RngCore};
+use std::convert::TryInto;
+
+use super::verify;
+use super::Round1Output;
+use crate::wire::{self, DecodeError};
+
+/// A complaint broadcast by `complainant` when the share it received from
+/// `accused` fails the Feldman equation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Complaint {
+    pub complainant: usize,
+    pub accused: usize,
+}
+
+impl Complaint {
+    /// Canonical wire encoding: `complainant` and `accused` as little-endian `u32`s.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let mut out = Vec::with_capacity(8);
+        wire::put_u32(&mut out, self.complainant as u32);
+        wire::put_u32(&mut out, self.accused as u32);
+        out.try_into().unwrap()
+    }
+
+    /// Decodes a [`Complaint`], rejecting a `0` index.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut pos = 0;
+        let complainant = wire::take_index(buf, &mut pos)?;
+        let accused = wire::take_index(buf, &mut pos)?;
+        wire::finish(buf, pos)?;
+        Ok(Complaint { complainant, accused })
+    }
+}
+
+/// `accused`'s response to a complaint: the disputed share, revealed in the clear.
+#[derive(Clone, Copy)]
+pub struct ComplaintResponse {
+    pub accused: usize,
+    pub complainant: usize,
+    pub revealed_share: Scalar,
+}
+
+impl ComplaintResponse {
+    /// Canonical wire encoding: `accused` and `complainant` as little-endian
+    /// `u32`s, then the canonical 32-byte revealed share.
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let mut out = Vec::with_capacity(40);
+        wire::put_u32(&mut out, self.accused as u32);
+        wire::put_u32(&mut out, self.complainant as u32);
+        wire::put_scalar(&mut out, &self.revealed_share);
+        out.try_into().unwrap()
+    }
+
+    /// Decodes a [`ComplaintResponse`], rejecting a `0` index or a
+    /// non-canonical revealed share.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut pos = 0;
+        let accused = wire::take_index(buf, &mut pos)?;
+        let complainant = wire::take_index(buf, &mut pos)?;
+        let revealed_share = wire::take_scalar(buf, &mut pos)?;
+        wire::finish(buf, pos)?;
+        Ok(ComplaintResponse {
+            accused,
+            complainant,
+            revealed_share,
+        })
+    }
+}
+
+/// Why a dealer was dropped from `QUAL`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisqualificationReason {
+    /// `accused` never responded to a complaint naming it.
+    NoResponse,
+    /// `accused` responded, but the revealed share fails the Feldman equation.
+    InconsistentShare,
+}
+
+/// The outcome of the complaint-resolution round: which dealers survive into
+/// `QUAL`, and why anyone else was disqualified.
+pub struct Resolution {
+    pub qual: Vec<usize>,
+    pub disqualified: Vec<(usize, DisqualificationReason)>,
+}
+
+/// Resolves a round of complaints against dealers `1..=n`, given their
+/// broadcast [`Round1Output`]s (`round1[i - 1]` is dealer `i`'s).
+///
+/// A dealer named in a complaint is disqualified unless it broadcasts a
+/// matching [`ComplaintResponse`] for every complaint against it, and every
+/// revealed share satisfies the Feldman equation at its complainant's index.
+/// Complaint resolution is the one point in the protocol where multiple
+/// recipients' shares for the same dealer are both revealed in the clear and
+/// visible to a single party, so every revealed share naming a given dealer
+/// is Feldman-checked together in one batched [`verify::batch_verify_dealer`]
+/// call instead of one multiscalar operation per complaint.
+///
+/// `complaints`/`responses` arrive over the untrusted transport `wire.rs`
+/// decodes, so a complaint naming an `accused` dealer outside `1..=n` is
+/// discarded rather than indexed into `round1` — the same class of fault
+/// `verify_and_aggregate`'s `InvalidQualSet` check guards against.
+pub fn resolve<R>(
+    csprng: &mut R,
+    n: usize,
+    round1: &[Round1Output],
+    complaints: &[Complaint],
+    responses: &[ComplaintResponse],
+) -> Resolution
+where
+    R: CryptoRng + RngCore,
+{
+    let mut disqualified: Vec<(usize, DisqualificationReason)> = Vec::new();
+
+    let mut accused_dealers: Vec<usize> = complaints
+        .iter()
+        .map(|c| c.accused)
+        .filter(|accused| (1..=n).contains(accused))
+        .collect();
+    accused_dealers.sort_unstable();
+    accused_dealers.dedup();
+
+    for accused in accused_dealers {
+        let mut revealed_shares = Vec::new();
+        let mut reason = None;
+
+        for complaint in complaints.iter().filter(|c| c.accused == accused) {
+            let response = responses
+                .iter()
+                .find(|r| r.accused == accused && r.complainant == complaint.complainant);
+
+            match response {
+                Some(response) => {
+                    revealed_shares.push((Scalar::from(complaint.complainant as u64), response.revealed_share));
+                }
+                None => {
+                    reason = Some(DisqualificationReason::NoResponse);
+                    break;
+                }
+            }
+        }
+
+        let reason = reason.or_else(|| {
+            let feldman_coeffs = &round1[accused - 1].feldman_coeffs;
+
+            if verify::batch_verify_dealer(csprng, feldman_coeffs, &revealed_shares) {
+                None
+            } else {
+                Some(DisqualificationReason::InconsistentShare)
+            }
+        });
+
+        if let Some(reason) = reason {
+            disqualified.push((accused, reason));
+        }
+    }
+
+    let qual = (1..=n)
+        .filter(|i| !disqualified.iter().any(|(d, _)| d == i))
+        .collect();
+
+    Resolution { qual, disqualified }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::DkgParticipant;
+
+    #[test]
+    fn dealer_with_no_complaints_stays_in_qual() {
+        let mut csprng = rand::thread_rng();
+        let mut dealer = DkgParticipant::new(1, 3, 2).unwrap();
+        let round1 = vec![dealer.round1_commit(&mut csprng)];
+
+        let resolution = resolve(&mut csprng, 1, &round1, &[], &[]);
+
+        assert_eq!(resolution.qual, vec![1]);
+        assert!(resolution.disqualified.is_empty());
+    }
+
+    #[test]
+    fn honest_dealer_with_correct_response_stays_in_qual() {
+        let mut csprng = rand::thread_rng();
+        let mut dealer = DkgParticipant::new(1, 3, 2).unwrap();
+        let round1 = vec![dealer.round1_commit(&mut csprng)];
+        let complaints = vec![Complaint { complainant: 2, accused: 1 }];
+        let responses = vec![dealer.respond_to_complaint(2).unwrap()];
+
+        let resolution = resolve(&mut csprng, 1, &round1, &complaints, &responses);
+
+        assert_eq!(resolution.qual, vec![1]);
+        assert!(resolution.disqualified.is_empty());
+    }
+
+    #[test]
+    fn dealer_with_no_response_is_disqualified() {
+        let mut csprng = rand::thread_rng();
+        let mut dealer = DkgParticipant::new(1, 3, 2).unwrap();
+        let round1 = vec![dealer.round1_commit(&mut csprng)];
+        let complaints = vec![Complaint { complainant: 2, accused: 1 }];
+
+        let resolution = resolve(&mut csprng, 1, &round1, &complaints, &[]);
+
+        assert!(resolution.qual.is_empty());
+        assert_eq!(resolution.disqualified, vec![(1, DisqualificationReason::NoResponse)]);
+    }
+
+    #[test]
+    fn dealer_with_inconsistent_revealed_share_is_disqualified() {
+        let mut csprng = rand::thread_rng();
+        let mut dealer = DkgParticipant::new(1, 3, 2).unwrap();
+        let round1 = vec![dealer.round1_commit(&mut csprng)];
+        let complaints = vec![Complaint { complainant: 2, accused: 1 }];
+
+        let mut bad_response = dealer.respond_to_complaint(2).unwrap();
+        bad_response.revealed_share += Scalar::one();
+
+        let resolution = resolve(&mut csprng, 1, &round1, &complaints, &[bad_response]);
+
+        assert!(resolution.qual.is_empty());
+        assert_eq!(
+            resolution.disqualified,
+            vec![(1, DisqualificationReason::InconsistentShare)]
+        );
+    }
+
+    #[test]
+    fn complaint_naming_an_out_of_range_dealer_is_discarded() {
+        let mut csprng = rand::thread_rng();
+        let mut dealer = DkgParticipant::new(1, 3, 2).unwrap();
+        let round1 = vec![dealer.round1_commit(&mut csprng)];
+        let complaints = vec![Complaint { complainant: 2, accused: 999_999 }];
+        let responses = vec![ComplaintResponse {
+            accused: 999_999,
+            complainant: 2,
+            revealed_share: Scalar::zero(),
+        }];
+
+        let resolution = resolve(&mut csprng, 1, &round1, &complaints, &responses);
+
+        assert_eq!(resolution.qual, vec![1]);
+        assert!(resolution.disqualified.is_empty());
+    }
+}
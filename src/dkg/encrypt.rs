@@ -0,0 +1,171 @@
+//! Ephemeral-key ECIES transport for Feldman shares dealt over an untrusted
+//! channel, so `s_i_j` never travels in the clear.
+
+use curve25519_dalek::{constants, edwards::EdwardsPoint, scalar::Scalar};
+use digest::Digest;
+use ed25519_dalek::Sha512;
+use rand::{CryptoRng, RngCore};
+use std::convert::TryInto;
+use subtle::ConstantTimeEq;
+
+use crate::wire::{self, DecodeError};
+
+/// A share encrypted for one recipient: an ephemeral Diffie-Hellman point,
+/// the masked share, and a MAC over the ciphertext.
+#[derive(Clone)]
+pub struct EncryptedShare {
+    pub ephemeral: EdwardsPoint,
+    pub ct: [u8; 32],
+    pub tag: [u8; 16],
+}
+
+/// Encrypts `share` for a recipient with public key `recipient_pk`.
+///
+/// Samples an ephemeral scalar `e`, publishes `E = e*B`, and derives the
+/// transport key from the Diffie-Hellman point `P = e*recipient_pk`.
+pub fn encrypt_share<R>(csprng: &mut R, recipient_pk: &EdwardsPoint, share: &Scalar) -> EncryptedShare
+where
+    R: CryptoRng + RngCore,
+{
+    let e = Scalar::random(csprng);
+    let ephemeral = &e * &constants::ED25519_BASEPOINT_TABLE;
+    let shared_point = e * recipient_pk;
+
+    let (mask, mac_key) = derive_keys(&ephemeral, &shared_point);
+
+    let mut ct = share.to_bytes();
+    xor_in_place(&mut ct, &mask);
+
+    let tag = mac(&mac_key, &ct);
+
+    EncryptedShare { ephemeral, ct, tag }
+}
+
+/// Decrypts a share dealt to the holder of `recipient_sk`, the secret scalar
+/// behind the public key the share was encrypted for.
+///
+/// Returns `None` if the MAC doesn't authenticate or the recovered bytes
+/// aren't a canonical scalar encoding.
+pub fn decrypt_share(recipient_sk: &Scalar, encrypted: &EncryptedShare) -> Option<Scalar> {
+    let shared_point = recipient_sk * encrypted.ephemeral;
+    let (mask, mac_key) = derive_keys(&encrypted.ephemeral, &shared_point);
+
+    if mac(&mac_key, &encrypted.ct).ct_eq(&encrypted.tag).unwrap_u8() == 0 {
+        return None;
+    }
+
+    let mut pt = encrypted.ct;
+    xor_in_place(&mut pt, &mask);
+
+    Scalar::from_canonical_bytes(pt)
+}
+
+fn derive_keys(ephemeral: &EdwardsPoint, shared_point: &EdwardsPoint) -> ([u8; 32], [u8; 32]) {
+    let mut hasher = Sha512::new();
+    hasher.update(ephemeral.compress().as_bytes());
+    hasher.update(shared_point.compress().as_bytes());
+    let digest = hasher.finalize();
+
+    let mut mask = [0u8; 32];
+    let mut mac_key = [0u8; 32];
+    mask.copy_from_slice(&digest[0..32]);
+    mac_key.copy_from_slice(&digest[32..64]);
+
+    (mask, mac_key)
+}
+
+fn mac(mac_key: &[u8; 32], ct: &[u8; 32]) -> [u8; 16] {
+    let mut hasher = Sha512::new();
+    hasher.update(mac_key);
+    hasher.update(ct);
+    let digest = hasher.finalize();
+
+    let mut tag = [0u8; 16];
+    tag.copy_from_slice(&digest[0..16]);
+    tag
+}
+
+fn xor_in_place(data: &mut [u8; 32], mask: &[u8; 32]) {
+    for (b, m) in data.iter_mut().zip(mask.iter()) {
+        *b ^= m;
+    }
+}
+
+impl EncryptedShare {
+    /// Canonical wire encoding: the compressed ephemeral point, the 32-byte
+    /// masked ciphertext, then the 16-byte MAC tag.
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut out = Vec::with_capacity(80);
+        wire::put_point(&mut out, &self.ephemeral);
+        out.extend_from_slice(&self.ct);
+        out.extend_from_slice(&self.tag);
+        out.try_into().unwrap()
+    }
+
+    /// Decodes an [`EncryptedShare`], rejecting an ephemeral point that
+    /// decompresses to the identity or another low-order point.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut pos = 0;
+        let ephemeral = wire::take_point(buf, &mut pos)?;
+        let ct: [u8; 32] = wire::take(buf, &mut pos, 32)?.try_into().unwrap();
+        let tag: [u8; 16] = wire::take(buf, &mut pos, 16)?.try_into().unwrap();
+        wire::finish(buf, pos)?;
+        Ok(EncryptedShare { ephemeral, ct, tag })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypts_to_the_original_share() {
+        let mut csprng = rand::thread_rng();
+        let recipient_sk = Scalar::random(&mut csprng);
+        let recipient_pk = &recipient_sk * &constants::ED25519_BASEPOINT_TABLE;
+        let share = Scalar::random(&mut csprng);
+
+        let encrypted = encrypt_share(&mut csprng, &recipient_pk, &share);
+
+        assert_eq!(decrypt_share(&recipient_sk, &encrypted), Some(share));
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails_the_mac() {
+        let mut csprng = rand::thread_rng();
+        let recipient_sk = Scalar::random(&mut csprng);
+        let recipient_pk = &recipient_sk * &constants::ED25519_BASEPOINT_TABLE;
+        let share = Scalar::random(&mut csprng);
+
+        let mut encrypted = encrypt_share(&mut csprng, &recipient_pk, &share);
+        encrypted.ct[0] ^= 1;
+
+        assert_eq!(decrypt_share(&recipient_sk, &encrypted), None);
+    }
+
+    #[test]
+    fn tampered_tag_fails_the_mac() {
+        let mut csprng = rand::thread_rng();
+        let recipient_sk = Scalar::random(&mut csprng);
+        let recipient_pk = &recipient_sk * &constants::ED25519_BASEPOINT_TABLE;
+        let share = Scalar::random(&mut csprng);
+
+        let mut encrypted = encrypt_share(&mut csprng, &recipient_pk, &share);
+        encrypted.tag[0] ^= 1;
+
+        assert_eq!(decrypt_share(&recipient_sk, &encrypted), None);
+    }
+
+    #[test]
+    fn wrong_recipient_key_fails_to_decrypt() {
+        let mut csprng = rand::thread_rng();
+        let recipient_sk = Scalar::random(&mut csprng);
+        let recipient_pk = &recipient_sk * &constants::ED25519_BASEPOINT_TABLE;
+        let other_sk = Scalar::random(&mut csprng);
+        let share = Scalar::random(&mut csprng);
+
+        let encrypted = encrypt_share(&mut csprng, &recipient_pk, &share);
+
+        assert_eq!(decrypt_share(&other_sk, &encrypted), None);
+    }
+}
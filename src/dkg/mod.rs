@@ -0,0 +1,387 @@
+//! Pedersen-commitment / Feldman-VSS distributed key generation.
+//!
+//! [`DkgParticipant`] drives one participant through the staged protocol:
+//! `round1_commit` (broadcast) -> `round2_shares` (deal privately, ECIES-encrypted
+//! per recipient) -> `check_shares` (decrypt, verify, and raise a [`Complaint`]
+//! per inconsistent dealer) -> out-of-band complaint resolution via
+//! [`complaint::resolve`] -> `verify_and_aggregate` (aggregate the surviving
+//! `QUAL` contributions) -> `finalize`.
+
+pub mod complaint;
+pub mod encrypt;
+pub mod pop;
+pub mod verify;
+
+use curve25519_dalek::{constants, edwards::EdwardsPoint, scalar::Scalar};
+use digest::Digest;
+use ed25519_dalek::Sha512;
+use rand::{CryptoRng, RngCore};
+use std::convert::TryInto;
+
+use crate::polynom::Polynom;
+use crate::wire::{self, DecodeError};
+use complaint::{Complaint, ComplaintResponse};
+use encrypt::EncryptedShare;
+use pop::ProofOfPossession;
+
+/// Domain-separation prefix used to derive the Pedersen blinding base point
+/// `H` from the Ed25519 basepoint, so every participant agrees on the same
+/// `H` without an extra setup round.
+const PEDERSEN_PREFIX: [u8; 32] = [255u8; 32];
+
+/// Errors produced while driving a [`DkgParticipant`] through the protocol.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DkgError {
+    /// `t` and `n` don't satisfy `1 <= t <= n`, or `index` is out of `1..=n`.
+    InvalidParameters { n: usize, t: usize },
+    /// A method was called before its prerequisite stage completed.
+    OutOfOrder(&'static str),
+    /// The share dealt by `dealer` failed to decrypt or failed the Feldman
+    /// verification equation.
+    FeldmanVerificationFailed { dealer: usize },
+    /// `verify_and_aggregate`/`check_shares` wasn't given exactly one
+    /// contribution per dealer, or `round2_shares` wasn't given exactly one
+    /// public key per recipient.
+    WrongDealerCount { expected: usize, got: usize },
+    /// `dealer`'s proof of possession of its own `h_i` did not verify, so its
+    /// contribution was rejected rather than summed into the public key.
+    InvalidProofOfPossession { dealer: usize },
+    /// `dealer` published a Feldman coefficient vector whose length doesn't
+    /// match the scheme's threshold `t`, which would silently change that
+    /// dealer's effective reconstruction threshold.
+    WrongCoefficientCount {
+        dealer: usize,
+        expected: usize,
+        got: usize,
+    },
+    /// `qual` named a dealer index outside `1..=n`, or named the same
+    /// dealer more than once.
+    InvalidQualSet { index: usize },
+}
+
+/// The Pedersen commitment and Feldman coefficients a dealer broadcasts in round 1.
+#[derive(Clone)]
+pub struct Round1Output {
+    /// `C_i = h_i + r_i*H`, the blinded commitment to this dealer's key share.
+    pub commitment: EdwardsPoint,
+    /// `F_i_0..F_i_{t-1}`, the exponentiated coefficients of this dealer's
+    /// secret-sharing polynomial. `F_i_0 == h_i`.
+    pub feldman_coeffs: Vec<EdwardsPoint>,
+    /// Proof that this dealer knows the discrete log of `feldman_coeffs[0]`,
+    /// defending against rogue-key attacks on the aggregate public key.
+    pub pop: ProofOfPossession,
+}
+
+impl Round1Output {
+    /// Canonical wire encoding: the compressed commitment, the length-prefixed
+    /// Feldman coefficients, then the proof of possession.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        wire::put_point(&mut out, &self.commitment);
+        wire::put_points(&mut out, &self.feldman_coeffs);
+        out.extend_from_slice(&self.pop.to_bytes());
+        out
+    }
+
+    /// Decodes a [`Round1Output`], rejecting any point that decompresses to
+    /// the identity or another low-order point.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut pos = 0;
+        let commitment = wire::take_point(buf, &mut pos)?;
+        let feldman_coeffs = wire::take_points(buf, &mut pos)?;
+        let pop = ProofOfPossession::from_bytes(&buf[pos..])?;
+
+        Ok(Round1Output {
+            commitment,
+            feldman_coeffs,
+            pop,
+        })
+    }
+}
+
+/// One dealer's contribution as seen by a recipient: the [`Round1Output`] it
+/// broadcast, plus the ECIES-encrypted share it privately dealt to the recipient.
+pub struct DealerContribution {
+    pub round1: Round1Output,
+    pub encrypted_share: EncryptedShare,
+}
+
+/// One participant's state machine through the DKG protocol.
+pub struct DkgParticipant {
+    index: usize,
+    n: usize,
+    t: usize,
+    x: Scalar,
+    sk: Option<Scalar>,
+    polynom: Option<Polynom>,
+    public_key: Option<EdwardsPoint>,
+    share: Option<Scalar>,
+}
+
+impl DkgParticipant {
+    /// Creates a new participant at 1-based `index` in a `t`-of-`n` scheme.
+    pub fn new(index: usize, n: usize, t: usize) -> Result<Self, DkgError> {
+        if t == 0 || t > n || index == 0 || index > n {
+            return Err(DkgError::InvalidParameters { n, t });
+        }
+
+        Ok(DkgParticipant {
+            index,
+            n,
+            t,
+            x: Scalar::from(index as u64),
+            sk: None,
+            polynom: None,
+            public_key: None,
+            share: None,
+        })
+    }
+
+    /// This participant's 1-based index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// This participant's evaluation point, i.e. its index as a scalar.
+    pub fn x(&self) -> Scalar {
+        self.x
+    }
+
+    fn pedersen_base() -> EdwardsPoint {
+        &Scalar::from_bytes_mod_order(PEDERSEN_PREFIX) * &constants::ED25519_BASEPOINT_TABLE
+    }
+
+    /// Round 1: samples this dealer's key pair `(x_i, h_i)` and secret-sharing
+    /// polynomial, and returns the Pedersen commitment plus Feldman
+    /// coefficients to broadcast to every other participant.
+    pub fn round1_commit<R>(&mut self, csprng: &mut R) -> Round1Output
+    where
+        R: CryptoRng + RngCore,
+    {
+        let (sk, pk) = generate_key_pair(csprng);
+        let blinder = Scalar::random(csprng);
+        let polynom = Polynom::random(csprng, &sk, self.t - 1);
+
+        let feldman_coeffs = polynom
+            .coeffs
+            .iter()
+            .map(|c| c * &constants::ED25519_BASEPOINT_TABLE)
+            .collect();
+
+        let commitment = pk + (blinder * Self::pedersen_base());
+        let pop = pop::prove(csprng, &sk, &pk, &pop_context(self.index, self.n, self.t));
+
+        self.sk = Some(sk);
+        self.polynom = Some(polynom);
+
+        Round1Output {
+            commitment,
+            feldman_coeffs,
+            pop,
+        }
+    }
+
+    /// Round 2: evaluates this dealer's polynomial at every recipient's index
+    /// and ECIES-encrypts each evaluation under the matching `recipient_pks[j]`
+    /// (the `h_j` each recipient published as `feldman_coeffs[0]` in round 1),
+    /// so `s_i_j` never travels in the clear.
+    pub fn round2_shares<R>(
+        &self,
+        recipient_pks: &[EdwardsPoint],
+        csprng: &mut R,
+    ) -> Result<Vec<EncryptedShare>, DkgError>
+    where
+        R: CryptoRng + RngCore,
+    {
+        let polynom = self
+            .polynom
+            .as_ref()
+            .ok_or(DkgError::OutOfOrder("round1_commit must run before round2_shares"))?;
+
+        if recipient_pks.len() != self.n {
+            return Err(DkgError::WrongDealerCount {
+                expected: self.n,
+                got: recipient_pks.len(),
+            });
+        }
+
+        Ok((1..=self.n)
+            .zip(recipient_pks)
+            .map(|(j, recipient_pk)| {
+                let share = polynom.at(&Scalar::from(j as u64));
+                encrypt::encrypt_share(csprng, recipient_pk, &share)
+            })
+            .collect())
+    }
+
+    /// Decrypts and Feldman-checks every dealer's share, returning a
+    /// [`Complaint`] against any dealer whose share fails to decrypt or
+    /// fails the Feldman equation, instead of aborting on the first failure.
+    /// An empty result means every dealer may go straight into `QUAL`.
+    pub fn check_shares(&self, dealers: &[DealerContribution]) -> Result<Vec<Complaint>, DkgError> {
+        if dealers.len() != self.n {
+            return Err(DkgError::WrongDealerCount {
+                expected: self.n,
+                got: dealers.len(),
+            });
+        }
+
+        let sk = self
+            .sk
+            .ok_or(DkgError::OutOfOrder("round1_commit must run before check_shares"))?;
+
+        let mut complaints = Vec::new();
+
+        for (position, dealer) in dealers.iter().enumerate() {
+            self.check_coefficient_count(position + 1, dealer)?;
+
+            if self.decrypt_and_check(&sk, dealer).is_none() {
+                complaints.push(Complaint {
+                    complainant: self.index,
+                    accused: position + 1,
+                });
+            }
+        }
+
+        Ok(complaints)
+    }
+
+    /// Reveals the share this dealer dealt to `complainant`, in the clear, so
+    /// the rest of the group can resolve a complaint against it.
+    pub fn respond_to_complaint(&self, complainant: usize) -> Result<ComplaintResponse, DkgError> {
+        let polynom = self
+            .polynom
+            .as_ref()
+            .ok_or(DkgError::OutOfOrder("round1_commit must run before respond_to_complaint"))?;
+
+        Ok(ComplaintResponse {
+            accused: self.index,
+            complainant,
+            revealed_share: polynom.at(&Scalar::from(complainant as u64)),
+        })
+    }
+
+    /// Aggregates this participant's final share and the group public key
+    /// from the dealers in `qual`, as decided by [`complaint::resolve`].
+    /// Dealers outside `qual` contribute nothing.
+    pub fn verify_and_aggregate(
+        &mut self,
+        dealers: &[DealerContribution],
+        qual: &[usize],
+    ) -> Result<(), DkgError> {
+        if dealers.len() != self.n {
+            return Err(DkgError::WrongDealerCount {
+                expected: self.n,
+                got: dealers.len(),
+            });
+        }
+
+        let sk = self
+            .sk
+            .ok_or(DkgError::OutOfOrder("round1_commit must run before verify_and_aggregate"))?;
+
+        let mut seen = std::collections::HashSet::with_capacity(qual.len());
+        for &dealer_index in qual {
+            if dealer_index == 0 || dealer_index > dealers.len() || !seen.insert(dealer_index) {
+                return Err(DkgError::InvalidQualSet { index: dealer_index });
+            }
+        }
+
+        let mut shares = Vec::with_capacity(qual.len());
+        let mut public_key_terms = Vec::with_capacity(qual.len());
+
+        for &dealer_index in qual {
+            let dealer = &dealers[dealer_index - 1];
+            self.check_coefficient_count(dealer_index, dealer)?;
+
+            let h_i = dealer.round1.feldman_coeffs[0];
+            let context = pop_context(dealer_index, self.n, self.t);
+
+            if !pop::verify(&h_i, &dealer.round1.pop, &context) {
+                return Err(DkgError::InvalidProofOfPossession { dealer: dealer_index });
+            }
+
+            let share = self
+                .decrypt_and_check(&sk, dealer)
+                .ok_or(DkgError::FeldmanVerificationFailed { dealer: dealer_index })?;
+
+            shares.push(share);
+            public_key_terms.push(h_i);
+        }
+
+        self.public_key = Some(public_key_terms.into_iter().sum());
+        self.share = Some(shares.into_iter().sum());
+
+        Ok(())
+    }
+
+    /// Rejects a dealer whose broadcast Feldman coefficients don't number
+    /// exactly `self.t`, which would otherwise let it silently deal shares
+    /// reconstructible below (or above) the agreed threshold.
+    fn check_coefficient_count(&self, dealer_index: usize, dealer: &DealerContribution) -> Result<(), DkgError> {
+        let got = dealer.round1.feldman_coeffs.len();
+
+        if got == self.t {
+            Ok(())
+        } else {
+            Err(DkgError::WrongCoefficientCount {
+                dealer: dealer_index,
+                expected: self.t,
+                got,
+            })
+        }
+    }
+
+    /// Decrypts `dealer`'s share with this participant's secret key and
+    /// checks it against `dealer`'s Feldman coefficients, returning `None` on
+    /// either failure.
+    fn decrypt_and_check(&self, sk: &Scalar, dealer: &DealerContribution) -> Option<Scalar> {
+        let share = encrypt::decrypt_share(sk, &dealer.encrypted_share)?;
+
+        if verify::verify_share(&dealer.round1.feldman_coeffs, &self.x, &share) {
+            Some(share)
+        } else {
+            None
+        }
+    }
+
+    /// Returns this participant's long-term share and the group public key,
+    /// once `verify_and_aggregate` has succeeded.
+    pub fn finalize(&self) -> Result<(Scalar, EdwardsPoint), DkgError> {
+        match (self.share, self.public_key) {
+            (Some(share), Some(public_key)) => Ok((share, public_key)),
+            _ => Err(DkgError::OutOfOrder(
+                "verify_and_aggregate must succeed before finalize",
+            )),
+        }
+    }
+}
+
+/// Binds a proof of possession to the dealer's position in this DKG session,
+/// so it can't be replayed for a different index or a different `(n, t)`.
+fn pop_context(index: usize, n: usize, t: usize) -> [u8; 24] {
+    let mut context = [0u8; 24];
+    context[0..8].copy_from_slice(&(index as u64).to_le_bytes());
+    context[8..16].copy_from_slice(&(n as u64).to_le_bytes());
+    context[16..24].copy_from_slice(&(t as u64).to_le_bytes());
+    context
+}
+
+fn generate_key_pair<R>(csprng: &mut R) -> (Scalar, EdwardsPoint)
+where
+    R: CryptoRng + RngCore,
+{
+    let mut bytes = [0u8; 32];
+    csprng.fill_bytes(&mut bytes);
+
+    bytes = Sha512::digest(&bytes)[00..32].try_into().unwrap();
+
+    // do a conversion as per RFC
+    bytes[0] &= 248;
+    bytes[31] &= 127;
+    bytes[31] |= 64;
+
+    let sk = Scalar::from_bits(bytes);
+
+    (sk, &sk * &constants::ED25519_BASEPOINT_TABLE)
+}
@@ -0,0 +1,123 @@
+//! Schnorr proof of possession, binding a dealer's contribution `h_i = x_i*B`
+//! to proof that the dealer actually knows `x_i`, so a rogue dealer can't
+//! choose its contribution as a function of everyone else's and bias the
+//! aggregate public key.
+
+use curve25519_dalek::{constants, edwards::EdwardsPoint, scalar::Scalar};
+use digest::Digest;
+use ed25519_dalek::Sha512;
+use rand::{CryptoRng, RngCore};
+use std::convert::TryInto;
+
+use crate::wire::{self, DecodeError};
+
+/// Domain separation tag for the proof-of-possession challenge hash, distinct
+/// from the tag used to derive the Pedersen blinding base point.
+const POP_DOMAIN: &[u8] = b"pedersen-curve25519-keygen/pop/v1";
+
+/// A Schnorr proof of knowledge of the discrete log of `pk`, bound to `context`.
+#[derive(Clone)]
+pub struct ProofOfPossession {
+    r: EdwardsPoint,
+    z: Scalar,
+}
+
+/// Proves knowledge of `sk` behind `pk = sk*B`, bound to `context`.
+pub fn prove<R>(csprng: &mut R, sk: &Scalar, pk: &EdwardsPoint, context: &[u8]) -> ProofOfPossession
+where
+    R: CryptoRng + RngCore,
+{
+    let k = Scalar::random(csprng);
+    let r = &k * &constants::ED25519_BASEPOINT_TABLE;
+    let c = challenge(pk, &r, context);
+    let z = k + c * sk;
+
+    ProofOfPossession { r, z }
+}
+
+/// Verifies a [`ProofOfPossession`] of `pk`, bound to `context`.
+pub fn verify(pk: &EdwardsPoint, proof: &ProofOfPossession, context: &[u8]) -> bool {
+    let c = challenge(pk, &proof.r, context);
+    &proof.z * &constants::ED25519_BASEPOINT_TABLE == proof.r + c * pk
+}
+
+impl ProofOfPossession {
+    /// Canonical wire encoding: the compressed commitment `R`, then the
+    /// canonical 32-byte response scalar `z`.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut out = Vec::with_capacity(64);
+        wire::put_point(&mut out, &self.r);
+        wire::put_scalar(&mut out, &self.z);
+        out.try_into().unwrap()
+    }
+
+    /// Decodes a [`ProofOfPossession`], rejecting a non-canonical `z` or an
+    /// `R` that decompresses to the identity or another low-order point.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        let mut pos = 0;
+        let r = wire::take_point(buf, &mut pos)?;
+        let z = wire::take_scalar(buf, &mut pos)?;
+        wire::finish(buf, pos)?;
+        Ok(ProofOfPossession { r, z })
+    }
+}
+
+fn challenge(pk: &EdwardsPoint, r: &EdwardsPoint, context: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(POP_DOMAIN);
+    hasher.update(pk.compress().as_bytes());
+    hasher.update(r.compress().as_bytes());
+    hasher.update(context);
+    Scalar::from_hash(hasher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn genuine_proof_verifies() {
+        let mut csprng = rand::thread_rng();
+        let sk = Scalar::random(&mut csprng);
+        let pk = &sk * &constants::ED25519_BASEPOINT_TABLE;
+        let proof = prove(&mut csprng, &sk, &pk, b"context");
+
+        assert!(verify(&pk, &proof, b"context"));
+    }
+
+    #[test]
+    fn forged_proof_is_rejected() {
+        let mut csprng = rand::thread_rng();
+        let sk = Scalar::random(&mut csprng);
+        let pk = &sk * &constants::ED25519_BASEPOINT_TABLE;
+
+        let forged = ProofOfPossession {
+            r: constants::ED25519_BASEPOINT_POINT,
+            z: Scalar::random(&mut csprng),
+        };
+
+        assert!(!verify(&pk, &forged, b"context"));
+    }
+
+    #[test]
+    fn proof_does_not_verify_a_different_key() {
+        let mut csprng = rand::thread_rng();
+        let sk = Scalar::random(&mut csprng);
+        let pk = &sk * &constants::ED25519_BASEPOINT_TABLE;
+        let proof = prove(&mut csprng, &sk, &pk, b"context");
+
+        let other_pk = &Scalar::random(&mut csprng) * &constants::ED25519_BASEPOINT_TABLE;
+
+        assert!(!verify(&other_pk, &proof, b"context"));
+    }
+
+    #[test]
+    fn proof_cannot_be_replayed_under_a_different_context() {
+        let mut csprng = rand::thread_rng();
+        let sk = Scalar::random(&mut csprng);
+        let pk = &sk * &constants::ED25519_BASEPOINT_TABLE;
+        let proof = prove(&mut csprng, &sk, &pk, b"session-a");
+
+        assert!(!verify(&pk, &proof, b"session-b"));
+    }
+}
@@ -0,0 +1,164 @@
+//! Variable-time multiscalar verification of Feldman shares.
+//!
+//! Verifying a share only touches public data (Feldman coefficients and
+//! revealed evaluations), so variable-time scalar multiplication is safe
+//! here and lets a verifier collapse the `O(t)` individual point
+//! multiplications per share into a single multiscalar operation, and an
+//! entire dealer's shares across many recipients into one multiscalar
+//! operation regardless of how many recipients there are.
+
+use curve25519_dalek::edwards::{EdwardsPoint, VartimeEdwardsPrecomputation};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{Identity, VartimeMultiscalarMul, VartimePrecomputedMultiscalarMul};
+use rand::{CryptoRng, RngCore};
+
+use crate::pow::Pow;
+
+/// Checks a single Feldman share: `share*B == sum_k x^k * feldman_coeffs[k]`,
+/// via one multiscalar multiplication instead of `t` individual ones.
+pub fn verify_share(feldman_coeffs: &[EdwardsPoint], x: &Scalar, share: &Scalar) -> bool {
+    let powers: Vec<Scalar> = (0..feldman_coeffs.len() as u64).map(|k| x.pow(k)).collect();
+    let expected = EdwardsPoint::vartime_multiscalar_mul(powers.iter(), feldman_coeffs.iter());
+
+    expected == share * &curve25519_dalek::constants::ED25519_BASEPOINT_TABLE
+}
+
+/// A dealer's Feldman coefficients, precomputed for repeated variable-time
+/// verification against many recipients' shares.
+pub struct DealerBasis {
+    precomputed: VartimeEdwardsPrecomputation,
+    degree: usize,
+}
+
+impl DealerBasis {
+    pub fn new(feldman_coeffs: &[EdwardsPoint]) -> Self {
+        DealerBasis {
+            precomputed: VartimeEdwardsPrecomputation::new(feldman_coeffs.iter().copied()),
+            degree: feldman_coeffs.len(),
+        }
+    }
+
+    /// Checks one recipient's share against this dealer's precomputed basis.
+    pub fn verify(&self, x: &Scalar, share: &Scalar) -> bool {
+        let powers: Vec<Scalar> = (0..self.degree as u64).map(|k| x.pow(k)).collect();
+        let expected = self.precomputed.vartime_multiscalar_mul(powers);
+
+        expected == share * &curve25519_dalek::constants::ED25519_BASEPOINT_TABLE
+    }
+}
+
+/// Batch-verifies every recipient's share dealt by one dealer in a single
+/// multiscalar operation.
+///
+/// Samples random weights `rho_j` and checks `sum_j rho_j*(eval_j - s_j*B)
+/// == 0`, which, except with negligible probability, holds only if every
+/// individual Feldman equation `eval_j == s_j*B` holds. A batch failure
+/// doesn't say which recipient's share is bad; fall back to
+/// [`DealerBasis::verify`] (or [`verify_share`]) per recipient to find it.
+pub fn batch_verify_dealer<R>(csprng: &mut R, feldman_coeffs: &[EdwardsPoint], recipients: &[(Scalar, Scalar)]) -> bool
+where
+    R: CryptoRng + RngCore,
+{
+    let degree = feldman_coeffs.len();
+    let mut coeff_weights = vec![Scalar::zero(); degree];
+    let mut basepoint_weight = Scalar::zero();
+
+    for (x_j, share_j) in recipients {
+        let rho_j = Scalar::random(csprng);
+
+        for (k, weight) in coeff_weights.iter_mut().enumerate() {
+            *weight += rho_j * x_j.pow(k as u64);
+        }
+
+        basepoint_weight -= rho_j * share_j;
+    }
+
+    let mut scalars = coeff_weights;
+    scalars.push(basepoint_weight);
+
+    let mut points: Vec<EdwardsPoint> = feldman_coeffs.to_vec();
+    points.push(curve25519_dalek::constants::ED25519_BASEPOINT_POINT);
+
+    let result = EdwardsPoint::vartime_multiscalar_mul(scalars, points);
+
+    result == EdwardsPoint::identity()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynom::Polynom;
+
+    /// Builds a degree-`t - 1` Feldman-VSS setup: a random polynomial, its
+    /// exponentiated coefficients, and the `(x, share)` pairs for `1..=n`.
+    fn feldman_setup<R>(csprng: &mut R, t: usize, n: usize) -> (Vec<EdwardsPoint>, Vec<(Scalar, Scalar)>)
+    where
+        R: CryptoRng + RngCore,
+    {
+        let secret = Scalar::random(csprng);
+        let poly = Polynom::random(csprng, &secret, t - 1);
+        let feldman_coeffs: Vec<EdwardsPoint> = poly
+            .coeffs
+            .iter()
+            .map(|c| c * &curve25519_dalek::constants::ED25519_BASEPOINT_TABLE)
+            .collect();
+
+        let recipients: Vec<(Scalar, Scalar)> = (1..=n as u64)
+            .map(|i| {
+                let x = Scalar::from(i);
+                (x, poly.at(&x))
+            })
+            .collect();
+
+        (feldman_coeffs, recipients)
+    }
+
+    #[test]
+    fn verify_share_accepts_a_genuine_share() {
+        let mut csprng = rand::thread_rng();
+        let (feldman_coeffs, recipients) = feldman_setup(&mut csprng, 3, 1);
+        let (x, share) = recipients[0];
+
+        assert!(verify_share(&feldman_coeffs, &x, &share));
+    }
+
+    #[test]
+    fn verify_share_rejects_a_wrong_share() {
+        let mut csprng = rand::thread_rng();
+        let (feldman_coeffs, recipients) = feldman_setup(&mut csprng, 3, 1);
+        let (x, share) = recipients[0];
+
+        assert!(!verify_share(&feldman_coeffs, &x, &(share + Scalar::one())));
+    }
+
+    #[test]
+    fn dealer_basis_verify_agrees_with_verify_share() {
+        let mut csprng = rand::thread_rng();
+        let (feldman_coeffs, recipients) = feldman_setup(&mut csprng, 3, 2);
+        let basis = DealerBasis::new(&feldman_coeffs);
+
+        for (x, share) in &recipients {
+            assert!(basis.verify(x, share));
+        }
+
+        let (x, share) = recipients[0];
+        assert!(!basis.verify(&x, &(share + Scalar::one())));
+    }
+
+    #[test]
+    fn batch_verify_dealer_accepts_every_genuine_share() {
+        let mut csprng = rand::thread_rng();
+        let (feldman_coeffs, recipients) = feldman_setup(&mut csprng, 3, 5);
+
+        assert!(batch_verify_dealer(&mut csprng, &feldman_coeffs, &recipients));
+    }
+
+    #[test]
+    fn batch_verify_dealer_rejects_one_bad_share_among_many() {
+        let mut csprng = rand::thread_rng();
+        let (feldman_coeffs, mut recipients) = feldman_setup(&mut csprng, 3, 5);
+        recipients[2].1 += Scalar::one();
+
+        assert!(!batch_verify_dealer(&mut csprng, &feldman_coeffs, &recipients));
+    }
+}
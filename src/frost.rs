@@ -0,0 +1,235 @@
+//! Two-round FROST-style threshold Schnorr/EdDSA signing over the shares
+//! produced by [`crate::dkg`].
+//!
+//! Round 1: each signer samples nonces `(d_i, e_i)` and publishes
+//! `(D_i, E_i)`. Round 2: given the message and every signer's round-1
+//! commitments, each signer computes its binding factor, the group nonce,
+//! the challenge, and its response `z_i`. A coordinator then sums the `z_i`
+//! into a standard Ed25519 signature `(R, z)`.
+
+use curve25519_dalek::{constants, edwards::EdwardsPoint, scalar::Scalar};
+use digest::Digest;
+use ed25519_dalek::Sha512;
+use rand::{CryptoRng, RngCore};
+
+use crate::lagrange::lagrange_coeffs_at_zero;
+
+/// Errors produced while driving a [`FrostSigner`] through a signing session.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FrostError {
+    /// A method was called before its prerequisite stage completed.
+    OutOfOrder(&'static str),
+    /// This signer's index isn't among the signing set's commitments.
+    NotInSigningSet,
+}
+
+/// One signer's published nonce commitments for a signing session.
+#[derive(Clone, Copy)]
+pub struct NonceCommitments {
+    pub d: EdwardsPoint,
+    pub e: EdwardsPoint,
+}
+
+/// A FROST-produced signature, verifiable as an ordinary Ed25519 signature.
+#[derive(Clone, Copy)]
+pub struct Signature {
+    pub r: EdwardsPoint,
+    pub z: Scalar,
+}
+
+/// One signer's state machine through a FROST signing session, built from a
+/// long-term share produced by [`crate::dkg::DkgParticipant::finalize`].
+pub struct FrostSigner {
+    index: usize,
+    share: Scalar,
+    public_key: EdwardsPoint,
+    nonces: Option<(Scalar, Scalar)>,
+}
+
+impl FrostSigner {
+    /// Creates a signer from its long-term DKG `share`, `index`, and the
+    /// group's `public_key`.
+    pub fn new(index: usize, share: Scalar, public_key: EdwardsPoint) -> Self {
+        FrostSigner {
+            index,
+            share,
+            public_key,
+            nonces: None,
+        }
+    }
+
+    /// Round 1: samples fresh nonces `(d_i, e_i)` and returns the
+    /// commitments `(D_i, E_i)` to publish to the rest of the signing set.
+    pub fn round1_commit<R>(&mut self, csprng: &mut R) -> NonceCommitments
+    where
+        R: CryptoRng + RngCore,
+    {
+        let d = Scalar::random(csprng);
+        let e = Scalar::random(csprng);
+        self.nonces = Some((d, e));
+
+        NonceCommitments {
+            d: &d * &constants::ED25519_BASEPOINT_TABLE,
+            e: &e * &constants::ED25519_BASEPOINT_TABLE,
+        }
+    }
+
+    /// Round 2: computes this signer's response `z_i` over `message`, given
+    /// every signer's round-1 commitments (including this signer's own),
+    /// keyed by index.
+    pub fn round2_sign(
+        &self,
+        message: &[u8],
+        commitments: &[(usize, NonceCommitments)],
+    ) -> Result<Scalar, FrostError> {
+        let (d, e) = self
+            .nonces
+            .ok_or(FrostError::OutOfOrder("round1_commit must run before round2_sign"))?;
+
+        let position = commitments
+            .iter()
+            .position(|(i, _)| *i == self.index)
+            .ok_or(FrostError::NotInSigningSet)?;
+
+        let binding_factors = binding_factors(message, commitments);
+        let r = group_nonce(commitments, &binding_factors);
+        let c = challenge(&r, &self.public_key, message);
+
+        let xs: Vec<Scalar> = commitments.iter().map(|(i, _)| Scalar::from(*i as u64)).collect();
+        let lambda_i = lagrange_coeffs_at_zero(&xs)[position];
+        let rho_i = binding_factors[position];
+
+        Ok(d + rho_i * e + c * lambda_i * self.share)
+    }
+}
+
+/// Aggregates every signer's `z_i` response (from [`FrostSigner::round2_sign`])
+/// into a standard Ed25519-verifiable [`Signature`].
+pub fn aggregate(message: &[u8], commitments: &[(usize, NonceCommitments)], z_shares: &[Scalar]) -> Signature {
+    let binding_factors = binding_factors(message, commitments);
+    let r = group_nonce(commitments, &binding_factors);
+    let z = z_shares.iter().sum();
+
+    Signature { r, z }
+}
+
+/// Verifies a FROST-produced [`Signature`] as an ordinary Ed25519 signature.
+pub fn verify(public_key: &EdwardsPoint, message: &[u8], signature: &Signature) -> bool {
+    let c = challenge(&signature.r, public_key, message);
+
+    &signature.z * &constants::ED25519_BASEPOINT_TABLE == signature.r + c * public_key
+}
+
+/// Per-signer binding factor `rho_i = H(i, m, {D_j, E_j})`, preventing
+/// Wagner's-algorithm-style forgeries against naively summed nonces.
+fn binding_factors(message: &[u8], commitments: &[(usize, NonceCommitments)]) -> Vec<Scalar> {
+    commitments
+        .iter()
+        .map(|(i, _)| {
+            let mut hasher = Sha512::new();
+            hasher.update(b"pedersen-curve25519-keygen/frost/rho/v1");
+            hasher.update((*i as u64).to_le_bytes());
+            hasher.update(message);
+            for (j, c) in commitments {
+                hasher.update((*j as u64).to_le_bytes());
+                hasher.update(c.d.compress().as_bytes());
+                hasher.update(c.e.compress().as_bytes());
+            }
+            Scalar::from_hash(hasher)
+        })
+        .collect()
+}
+
+/// The group nonce `R = sum_j (D_j + rho_j*E_j)`.
+fn group_nonce(commitments: &[(usize, NonceCommitments)], binding_factors: &[Scalar]) -> EdwardsPoint {
+    commitments
+        .iter()
+        .zip(binding_factors)
+        .map(|((_, c), rho)| c.d + rho * c.e)
+        .sum()
+}
+
+/// The Ed25519 challenge `c = H(R || public_key || m)`.
+fn challenge(r: &EdwardsPoint, public_key: &EdwardsPoint, message: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(r.compress().as_bytes());
+    hasher.update(public_key.compress().as_bytes());
+    hasher.update(message);
+    Scalar::from_hash(hasher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::{DealerContribution, DkgParticipant};
+    use rand::seq::SliceRandom;
+
+    #[test]
+    fn threshold_signature_verifies_over_a_random_subset() {
+        const N: usize = 5;
+        const T: usize = 3;
+
+        let mut csprng = rand::thread_rng();
+
+        let mut participants: Vec<DkgParticipant> =
+            (1..=N).map(|i| DkgParticipant::new(i, N, T).unwrap()).collect();
+
+        let round1: Vec<_> = participants.iter_mut().map(|p| p.round1_commit(&mut csprng)).collect();
+        let recipient_pks: Vec<_> = round1.iter().map(|r| r.feldman_coeffs[0]).collect();
+        let shares: Vec<Vec<_>> = participants
+            .iter()
+            .map(|p| p.round2_shares(&recipient_pks, &mut csprng).unwrap())
+            .collect();
+
+        let qual: Vec<usize> = (1..=N).collect();
+        let mut final_shares = Vec::with_capacity(N);
+        let mut group_public_key = None;
+
+        for j in 0..N {
+            let dealers: Vec<_> = round1
+                .iter()
+                .zip(shares.iter())
+                .map(|(r1, s)| DealerContribution {
+                    round1: r1.clone(),
+                    encrypted_share: s[j].clone(),
+                })
+                .collect();
+
+            let complaints = participants[j].check_shares(&dealers).unwrap();
+            assert!(complaints.is_empty());
+
+            participants[j].verify_and_aggregate(&dealers, &qual).unwrap();
+
+            let (share, public_key) = participants[j].finalize().unwrap();
+            final_shares.push(share);
+            group_public_key = Some(public_key);
+        }
+
+        let group_public_key = group_public_key.unwrap();
+
+        let mut signing_set: Vec<usize> = (1..=N).collect();
+        signing_set.shuffle(&mut csprng);
+        signing_set.truncate(T);
+
+        let mut signers: Vec<FrostSigner> = signing_set
+            .iter()
+            .map(|&i| FrostSigner::new(i, final_shares[i - 1], group_public_key))
+            .collect();
+
+        let commitments: Vec<(usize, NonceCommitments)> = signers
+            .iter_mut()
+            .map(|s| (s.index, s.round1_commit(&mut csprng)))
+            .collect();
+
+        let message = b"frost threshold signature test";
+
+        let z_shares: Vec<_> = signers
+            .iter()
+            .map(|s| s.round2_sign(message, &commitments).unwrap())
+            .collect();
+
+        let signature = aggregate(message, &commitments, &z_shares);
+
+        assert!(verify(&group_public_key, message, &signature));
+    }
+}
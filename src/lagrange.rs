@@ -0,0 +1,25 @@
+use curve25519_dalek::scalar::Scalar;
+
+/// Lagrange coefficients for interpolating to `x = 0` given evaluation points
+/// `xs`, i.e. the weights `lambda_i` such that `sum_i lambda_i * f(xs[i]) ==
+/// f(0)` for any polynomial passing through those points.
+pub fn lagrange_coeffs_at_zero(xs: &[Scalar]) -> Vec<Scalar> {
+    xs.iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            xs.iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(Scalar::one(), |acc, (_, xj)| acc * (xj * (xj - xi).invert()))
+        })
+        .collect()
+}
+
+/// Reconstructs `f(0)` from `(x_i, share_i)` pairs via Lagrange interpolation.
+pub fn shamir_reconstruct(xs: &[Scalar], shares: &[Scalar]) -> Scalar {
+    lagrange_coeffs_at_zero(xs)
+        .iter()
+        .zip(shares.iter())
+        .map(|(c, s)| c * s)
+        .sum()
+}
@@ -0,0 +1,9 @@
+//! A configurable Pedersen-commitment / Feldman-VSS distributed key
+//! generation (DKG) library over curve25519.
+
+pub mod dkg;
+pub mod frost;
+pub mod lagrange;
+pub mod polynom;
+pub mod pow;
+pub mod wire;
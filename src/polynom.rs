@@ -0,0 +1,34 @@
+use curve25519_dalek::scalar::Scalar;
+use rand::{CryptoRng, RngCore};
+
+use crate::pow::Pow;
+
+/// A secret-sharing polynomial over the scalar field,
+/// `f(x) = coeffs[0] + coeffs[1]*x + ... + coeffs[degree]*x^degree`.
+pub struct Polynom {
+    pub coeffs: Vec<Scalar>,
+}
+
+impl Polynom {
+    /// Samples a random polynomial of the given `degree` whose free term is `secret`.
+    pub fn random<R>(csprng: &mut R, secret: &Scalar, degree: usize) -> Self
+    where
+        R: CryptoRng + RngCore,
+    {
+        let mut coeffs = Vec::with_capacity(degree + 1);
+        coeffs.push(*secret);
+        for _ in 0..degree {
+            coeffs.push(Scalar::random(csprng));
+        }
+        Polynom { coeffs }
+    }
+
+    /// Evaluates the polynomial at `x`.
+    pub fn at(&self, x: &Scalar) -> Scalar {
+        self.coeffs
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c * x.pow(i as u64))
+            .sum()
+    }
+}
@@ -0,0 +1,25 @@
+use curve25519_dalek::scalar::Scalar;
+
+/// Exponentiates a [`Scalar`] by a small non-negative integer.
+///
+/// `curve25519_dalek::Scalar` has no built-in `pow`; this is used to evaluate
+/// powers of participant indices when checking the Feldman equation.
+pub trait Pow {
+    fn pow(&self, exp: u64) -> Scalar;
+}
+
+impl Pow for Scalar {
+    fn pow(&self, exp: u64) -> Scalar {
+        let mut result = Scalar::one();
+        let mut base = *self;
+        let mut e = exp;
+        while e > 0 {
+            if e & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            e >>= 1;
+        }
+        result
+    }
+}
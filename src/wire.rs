@@ -0,0 +1,334 @@
+//! Canonical wire encoding for DKG protocol messages.
+//!
+//! Every message is encoded as compressed Edwards points and canonical
+//! 32-byte little-endian scalars, with `u32`-length-prefixed vectors.
+//! Decoding is strict: non-canonical scalars, identity or other low-order
+//! points where a point must generate the group, and wrong-length buffers
+//! are all rejected rather than silently accepted.
+//!
+//! [`DkgMessage`] tags each round's payload with a leading byte so a
+//! transport can route round-1 broadcasts, round-2 shares, and complaint
+//! messages without out-of-band framing.
+
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use std::convert::TryInto;
+
+#[cfg(test)]
+use curve25519_dalek::constants;
+
+use crate::dkg::complaint::{Complaint, ComplaintResponse};
+use crate::dkg::encrypt::EncryptedShare;
+use crate::dkg::Round1Output;
+
+/// Errors produced while decoding a wire-format message.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer ended before the encoding said it would.
+    UnexpectedEnd,
+    /// Extra bytes remained after decoding a complete message.
+    TrailingBytes,
+    /// A 32-byte scalar encoding wasn't canonical.
+    NonCanonicalScalar,
+    /// A compressed point failed to decompress, or decompressed to the
+    /// identity or another low-order point where that isn't allowed.
+    InvalidPoint,
+    /// A 1-based participant index decoded as `0`.
+    InvalidIndex,
+    /// The message's leading tag byte didn't match any known variant.
+    UnknownTag(u8),
+}
+
+/// Decodes a `u32` as a 1-based participant index, rejecting `0`.
+pub(crate) fn take_index(buf: &[u8], pos: &mut usize) -> Result<usize, DecodeError> {
+    match take_u32(buf, pos)? as usize {
+        0 => Err(DecodeError::InvalidIndex),
+        index => Ok(index),
+    }
+}
+
+pub(crate) fn put_u32(out: &mut Vec<u8>, n: u32) {
+    out.extend_from_slice(&n.to_le_bytes());
+}
+
+pub(crate) fn take<'a>(buf: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8], DecodeError> {
+    let end = pos.checked_add(n).ok_or(DecodeError::UnexpectedEnd)?;
+    let slice = buf.get(*pos..end).ok_or(DecodeError::UnexpectedEnd)?;
+    *pos = end;
+    Ok(slice)
+}
+
+pub(crate) fn take_u32(buf: &[u8], pos: &mut usize) -> Result<u32, DecodeError> {
+    let bytes: [u8; 4] = take(buf, pos, 4)?.try_into().unwrap();
+    Ok(u32::from_le_bytes(bytes))
+}
+
+pub(crate) fn put_scalar(out: &mut Vec<u8>, s: &Scalar) {
+    out.extend_from_slice(&s.to_bytes());
+}
+
+pub(crate) fn take_scalar(buf: &[u8], pos: &mut usize) -> Result<Scalar, DecodeError> {
+    let bytes: [u8; 32] = take(buf, pos, 32)?.try_into().unwrap();
+    Scalar::from_canonical_bytes(bytes).ok_or(DecodeError::NonCanonicalScalar)
+}
+
+pub(crate) fn put_point(out: &mut Vec<u8>, p: &EdwardsPoint) {
+    out.extend_from_slice(p.compress().as_bytes());
+}
+
+/// Decodes a compressed point, rejecting the identity and other low-order
+/// points: every point on the wire is expected to generate the group.
+pub(crate) fn take_point(buf: &[u8], pos: &mut usize) -> Result<EdwardsPoint, DecodeError> {
+    let bytes: [u8; 32] = take(buf, pos, 32)?.try_into().unwrap();
+    let point = CompressedEdwardsY(bytes).decompress().ok_or(DecodeError::InvalidPoint)?;
+
+    if point.is_small_order() {
+        return Err(DecodeError::InvalidPoint);
+    }
+
+    Ok(point)
+}
+
+pub(crate) fn put_points(out: &mut Vec<u8>, points: &[EdwardsPoint]) {
+    put_u32(out, points.len() as u32);
+    for p in points {
+        put_point(out, p);
+    }
+}
+
+pub(crate) fn take_points(buf: &[u8], pos: &mut usize) -> Result<Vec<EdwardsPoint>, DecodeError> {
+    let len = take_u32(buf, pos)? as usize;
+    (0..len).map(|_| take_point(buf, pos)).collect()
+}
+
+pub(crate) fn finish(buf: &[u8], pos: usize) -> Result<(), DecodeError> {
+    if pos == buf.len() {
+        Ok(())
+    } else {
+        Err(DecodeError::TrailingBytes)
+    }
+}
+
+/// A tagged DKG protocol message, giving a transport layer a stable way to
+/// route round-1 broadcasts, round-2 shares, and complaint-round messages.
+pub enum DkgMessage {
+    /// `from`'s round-1 broadcast: Pedersen commitment, Feldman
+    /// coefficients, and proof of possession.
+    Round1 { from: usize, output: Round1Output },
+    /// The ECIES-encrypted share `from` privately dealt to `to`.
+    Round2Share {
+        from: usize,
+        to: usize,
+        share: EncryptedShare,
+    },
+    /// A complaint broadcast against an inconsistent dealer.
+    Complaint(Complaint),
+    /// A dealer's response to a complaint, revealing the disputed share.
+    ComplaintResponse(ComplaintResponse),
+}
+
+impl DkgMessage {
+    const TAG_ROUND1: u8 = 1;
+    const TAG_ROUND2_SHARE: u8 = 2;
+    const TAG_COMPLAINT: u8 = 3;
+    const TAG_COMPLAINT_RESPONSE: u8 = 4;
+
+    /// Encodes this message, tag byte first.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        match self {
+            DkgMessage::Round1 { from, output } => {
+                out.push(Self::TAG_ROUND1);
+                put_u32(&mut out, *from as u32);
+                out.extend_from_slice(&output.to_bytes());
+            }
+            DkgMessage::Round2Share { from, to, share } => {
+                out.push(Self::TAG_ROUND2_SHARE);
+                put_u32(&mut out, *from as u32);
+                put_u32(&mut out, *to as u32);
+                out.extend_from_slice(&share.to_bytes());
+            }
+            DkgMessage::Complaint(complaint) => {
+                out.push(Self::TAG_COMPLAINT);
+                out.extend_from_slice(&complaint.to_bytes());
+            }
+            DkgMessage::ComplaintResponse(response) => {
+                out.push(Self::TAG_COMPLAINT_RESPONSE);
+                out.extend_from_slice(&response.to_bytes());
+            }
+        }
+
+        out
+    }
+
+    /// Decodes a tagged message, validating every point and scalar it
+    /// contains and rejecting trailing bytes.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, DecodeError> {
+        let (&tag, rest) = buf.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+
+        match tag {
+            Self::TAG_ROUND1 => {
+                let mut pos = 0;
+                let from = take_index(rest, &mut pos)?;
+                let output = Round1Output::from_bytes(&rest[pos..])?;
+                Ok(DkgMessage::Round1 { from, output })
+            }
+            Self::TAG_ROUND2_SHARE => {
+                let mut pos = 0;
+                let from = take_index(rest, &mut pos)?;
+                let to = take_index(rest, &mut pos)?;
+                let share = EncryptedShare::from_bytes(&rest[pos..])?;
+                Ok(DkgMessage::Round2Share { from, to, share })
+            }
+            Self::TAG_COMPLAINT => Ok(DkgMessage::Complaint(Complaint::from_bytes(rest)?)),
+            Self::TAG_COMPLAINT_RESPONSE => {
+                Ok(DkgMessage::ComplaintResponse(ComplaintResponse::from_bytes(rest)?))
+            }
+            other => Err(DecodeError::UnknownTag(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dkg::{encrypt, pop, DkgParticipant};
+
+    #[test]
+    fn round1_output_round_trips_through_dkg_message() {
+        let mut csprng = rand::thread_rng();
+        let mut participant = DkgParticipant::new(1, 3, 2).unwrap();
+        let output = participant.round1_commit(&mut csprng);
+
+        let message = DkgMessage::Round1 { from: 1, output: output.clone() };
+        let bytes = message.to_bytes();
+
+        match DkgMessage::from_bytes(&bytes).unwrap() {
+            DkgMessage::Round1 { from, output: decoded } => {
+                assert_eq!(from, 1);
+                assert_eq!(decoded.to_bytes(), output.to_bytes());
+            }
+            _ => panic!("wrong DkgMessage variant"),
+        }
+    }
+
+    #[test]
+    fn encrypted_share_round_trips_through_dkg_message() {
+        let mut csprng = rand::thread_rng();
+        let recipient_pk = constants::ED25519_BASEPOINT_POINT;
+        let share = encrypt::encrypt_share(&mut csprng, &recipient_pk, &Scalar::from(42u64));
+
+        let message = DkgMessage::Round2Share { from: 1, to: 2, share: share.clone() };
+        let bytes = message.to_bytes();
+
+        match DkgMessage::from_bytes(&bytes).unwrap() {
+            DkgMessage::Round2Share { from, to, share: decoded } => {
+                assert_eq!(from, 1);
+                assert_eq!(to, 2);
+                assert_eq!(decoded.to_bytes(), share.to_bytes());
+            }
+            _ => panic!("wrong DkgMessage variant"),
+        }
+    }
+
+    #[test]
+    fn proof_of_possession_round_trips() {
+        let mut csprng = rand::thread_rng();
+        let sk = Scalar::random(&mut csprng);
+        let pk = &sk * &constants::ED25519_BASEPOINT_TABLE;
+        let proof = pop::prove(&mut csprng, &sk, &pk, b"context");
+
+        let decoded = pop::ProofOfPossession::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(decoded.to_bytes(), proof.to_bytes());
+        assert!(pop::verify(&pk, &decoded, b"context"));
+    }
+
+    #[test]
+    fn complaint_round_trips_through_dkg_message() {
+        let complaint = Complaint { complainant: 2, accused: 3 };
+        let message = DkgMessage::Complaint(complaint);
+
+        match DkgMessage::from_bytes(&message.to_bytes()).unwrap() {
+            DkgMessage::Complaint(decoded) => assert_eq!(decoded, complaint),
+            _ => panic!("wrong DkgMessage variant"),
+        }
+    }
+
+    #[test]
+    fn complaint_response_round_trips_through_dkg_message() {
+        let response = ComplaintResponse {
+            accused: 1,
+            complainant: 2,
+            revealed_share: Scalar::from(7u64),
+        };
+        let message = DkgMessage::ComplaintResponse(response);
+
+        match DkgMessage::from_bytes(&message.to_bytes()).unwrap() {
+            DkgMessage::ComplaintResponse(decoded) => {
+                assert_eq!(decoded.accused, response.accused);
+                assert_eq!(decoded.complainant, response.complainant);
+                assert_eq!(decoded.revealed_share, response.revealed_share);
+            }
+            _ => panic!("wrong DkgMessage variant"),
+        }
+    }
+
+    #[test]
+    fn dkg_message_rejects_trailing_bytes() {
+        let message = DkgMessage::Complaint(Complaint { complainant: 1, accused: 2 });
+        let mut bytes = message.to_bytes();
+        bytes.push(0);
+
+        match DkgMessage::from_bytes(&bytes) {
+            Err(e) => assert_eq!(e, DecodeError::TrailingBytes),
+            Ok(_) => panic!("expected TrailingBytes"),
+        }
+    }
+
+    #[test]
+    fn dkg_message_rejects_unknown_tag() {
+        let message = DkgMessage::Complaint(Complaint { complainant: 1, accused: 2 });
+        let mut bytes = message.to_bytes();
+        bytes[0] = 0xaa;
+
+        match DkgMessage::from_bytes(&bytes) {
+            Err(e) => assert_eq!(e, DecodeError::UnknownTag(0xaa)),
+            Ok(_) => panic!("expected UnknownTag"),
+        }
+    }
+
+    #[test]
+    fn dkg_message_rejects_truncated_buffer() {
+        let message = DkgMessage::Complaint(Complaint { complainant: 1, accused: 2 });
+        let bytes = message.to_bytes();
+
+        match DkgMessage::from_bytes(&bytes[..bytes.len() - 1]) {
+            Err(e) => assert_eq!(e, DecodeError::UnexpectedEnd),
+            Ok(_) => panic!("expected UnexpectedEnd"),
+        }
+    }
+
+    #[test]
+    fn take_scalar_rejects_non_canonical_encoding() {
+        let buf = [0xffu8; 32];
+        let mut pos = 0;
+        assert_eq!(take_scalar(&buf, &mut pos), Err(DecodeError::NonCanonicalScalar));
+    }
+
+    #[test]
+    fn take_point_rejects_identity() {
+        // the identity (0, 1) compresses to y = 1 with a clear sign bit
+        let mut buf = [0u8; 32];
+        buf[0] = 1;
+        let mut pos = 0;
+        assert_eq!(take_point(&buf, &mut pos), Err(DecodeError::InvalidPoint));
+    }
+
+    #[test]
+    fn take_rejects_wrong_length_buffer() {
+        let buf = [0u8; 4];
+        let mut pos = 0;
+        assert_eq!(take(&buf, &mut pos, 32), Err(DecodeError::UnexpectedEnd));
+    }
+}